@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+
+const ENV_OVERRIDE: &str = "SERVER_BINARY";
+const CONFIG_FILE_NAME: &str = "config.json";
+const PATH_LOOKUP_NAME_ENV: &str = "SERVER_BINARY_NAME";
+// `CARGO_PKG_NAME`-derived so the PATH fallback can't collide with an
+// unrelated `server` executable some other init script or runtime put on PATH.
+const DEFAULT_PATH_LOOKUP_NAME: &str = concat!(env!("CARGO_PKG_NAME"), "-server");
+
+#[derive(Deserialize, Default)]
+struct AppConfig {
+    server_binary: Option<String>,
+}
+
+/// Resolves the backend binary to launch, trying in order:
+/// 1. the `SERVER_BINARY` env var
+/// 2. a `server_binary` entry in the app config file
+/// 3. the bundled sidecar for the current target triple
+/// 4. the system `PATH`, via the `which` crate, looking up `SERVER_BINARY_NAME`
+///    (or the `<package>-server` default) rather than a generic name
+///
+/// Returns a structured error listing every location tried when none resolve.
+pub fn resolve_server_binary(app: &AppHandle) -> Result<PathBuf, String> {
+    let mut tried = Vec::new();
+
+    env_override(&mut tried)
+        .or_else(|| config_override(app, &mut tried))
+        .or_else(|| bundled_sidecar(app, &mut tried))
+        .or_else(|| path_lookup(&mut tried))
+        .ok_or_else(|| {
+            format!(
+                "Could not locate the backend server binary. Tried:\n  - {}",
+                tried.join("\n  - ")
+            )
+        })
+}
+
+fn env_override(tried: &mut Vec<String>) -> Option<PathBuf> {
+    let value = std::env::var(ENV_OVERRIDE).ok()?;
+    let path = PathBuf::from(&value);
+    if path.is_file() {
+        Some(path)
+    } else {
+        tried.push(format!("{} env var ({})", ENV_OVERRIDE, path.display()));
+        None
+    }
+}
+
+fn config_override(app: &AppHandle, tried: &mut Vec<String>) -> Option<PathBuf> {
+    let config_path = app.path().app_config_dir().ok()?.join(CONFIG_FILE_NAME);
+    let contents = std::fs::read_to_string(&config_path).ok()?;
+    let config: AppConfig = serde_json::from_str(&contents).ok()?;
+    let path = PathBuf::from(config.server_binary?);
+    if path.is_file() {
+        Some(path)
+    } else {
+        tried.push(format!("config file override ({})", path.display()));
+        None
+    }
+}
+
+fn bundled_sidecar(app: &AppHandle, tried: &mut Vec<String>) -> Option<PathBuf> {
+    let resource_dir = app.path().resource_dir().ok()?;
+
+    // Determine the binary name based on target triple
+    let binary_name = if cfg!(target_os = "macos") {
+        if cfg!(target_arch = "aarch64") {
+            "server-aarch64-apple-darwin"
+        } else {
+            "server-x86_64-apple-darwin"
+        }
+    } else if cfg!(target_os = "windows") {
+        "server-x86_64-pc-windows-msvc.exe"
+    } else {
+        "server-x86_64-unknown-linux-gnu"
+    };
+
+    let path = resource_dir.join("binaries").join(binary_name);
+    if path.is_file() {
+        Some(path)
+    } else {
+        tried.push(format!("bundled sidecar ({})", path.display()));
+        None
+    }
+}
+
+fn path_lookup(tried: &mut Vec<String>) -> Option<PathBuf> {
+    let name = std::env::var(PATH_LOOKUP_NAME_ENV)
+        .unwrap_or_else(|_| DEFAULT_PATH_LOOKUP_NAME.to_string());
+
+    which::which(&name).ok().or_else(|| {
+        tried.push(format!("system PATH lookup for `{}`", name));
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Both cases share one test function (rather than two `#[test]`s) because
+    // they mutate the process-wide `SERVER_BINARY` env var and `cargo test`
+    // runs tests in parallel by default - splitting them risks one case
+    // clobbering the other's env var mid-run.
+    #[test]
+    fn env_override_resolution() {
+        std::env::remove_var(ENV_OVERRIDE);
+        let mut tried = Vec::new();
+        assert!(env_override(&mut tried).is_none());
+        assert!(tried.is_empty(), "absent env var should not be recorded as tried");
+
+        std::env::set_var(ENV_OVERRIDE, "/path/that/does/not/exist");
+        let mut tried = Vec::new();
+        assert!(env_override(&mut tried).is_none());
+        assert_eq!(tried.len(), 1);
+
+        std::env::remove_var(ENV_OVERRIDE);
+    }
+
+    #[test]
+    fn path_lookup_records_a_tried_entry_when_not_found() {
+        std::env::set_var(PATH_LOOKUP_NAME_ENV, "definitely-not-a-real-binary-xyz");
+        let mut tried = Vec::new();
+        assert!(path_lookup(&mut tried).is_none());
+        assert_eq!(tried.len(), 1);
+        std::env::remove_var(PATH_LOOKUP_NAME_ENV);
+    }
+}
@@ -0,0 +1,161 @@
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{ChildStderr, ChildStdout};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+const LOG_FILE_NAME: &str = "backend.log";
+const LOG_ROTATE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// State tracking the on-disk log file that backend output is mirrored to.
+pub struct LogState(Mutex<PathBuf>);
+
+#[derive(Clone, Serialize)]
+struct LogLine {
+    stream: &'static str,
+    line: String,
+    ts: u128,
+}
+
+impl LogState {
+    /// Resolves the log directory under `app_data_dir()`, creating it if needed,
+    /// and returns the state ready to be `manage`d.
+    pub fn init(app: &AppHandle) -> Result<Self, String> {
+        let app_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+
+        let log_dir = app_dir.join("logs");
+        fs::create_dir_all(&log_dir)
+            .map_err(|e| format!("Failed to create log directory {:?}: {}", log_dir, e))?;
+
+        Ok(LogState(Mutex::new(log_dir.join(LOG_FILE_NAME))))
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+fn rotate_if_needed(path: &Path) {
+    let Ok(meta) = fs::metadata(path) else {
+        return;
+    };
+    if meta.len() < LOG_ROTATE_BYTES {
+        return;
+    }
+    let rotated = path.with_extension("log.1");
+    let _ = fs::rename(path, rotated);
+}
+
+fn append_line(path: &Path, stream: &str, line: &str) {
+    rotate_if_needed(path);
+    let file = OpenOptions::new().create(true).append(true).open(path);
+    if let Ok(mut file) = file {
+        let _ = writeln!(file, "[{}] {}", stream, line);
+    }
+}
+
+/// Spawns one reader thread per stream that forwards each line to the webview
+/// as a `backend-log` event and mirrors it into the rolling log file.
+///
+/// `Child::stdout`/`stderr` are the blocking `std::process` pipe handles (the
+/// rest of the backend state keeps a plain `std::process::Child` so `try_wait`
+/// and `kill` stay synchronous), so these are read on dedicated OS threads
+/// rather than handed to a tokio reader that expects `AsyncRead`.
+pub fn spawn_log_capture(app: AppHandle, stdout: ChildStdout, stderr: ChildStderr) {
+    spawn_stream_reader(app.clone(), stdout, "stdout");
+    spawn_stream_reader(app, stderr, "stderr");
+}
+
+fn spawn_stream_reader<R>(app: AppHandle, reader: R, stream: &'static str)
+where
+    R: std::io::Read + Send + 'static,
+{
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            match line {
+                Ok(line) => {
+                    let log_state = app.state::<LogState>();
+                    // Hold the lock across the whole rotate-then-write sequence so the
+                    // stdout and stderr reader threads can't interleave a rotation from
+                    // one with a write from the other against the same file.
+                    {
+                        let path = log_state.0.lock().unwrap();
+                        append_line(&path, stream, &line);
+                    }
+
+                    let _ = app.emit(
+                        "backend-log",
+                        LogLine {
+                            stream,
+                            line,
+                            ts: now_millis(),
+                        },
+                    );
+                }
+                Err(e) => {
+                    eprintln!("Failed to read backend {} stream: {}", stream, e);
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Returns the path to the current rolling backend log file so the frontend can
+/// read and attach it to a bug report.
+#[tauri::command]
+pub fn get_last_log_file(state: tauri::State<LogState>) -> Result<String, String> {
+    let path = state.0.lock().unwrap().clone();
+    if !path.exists() {
+        return Err(format!("No log file found at {:?}", path));
+    }
+    path.into_os_string()
+        .into_string()
+        .map_err(|_| "Log file path is not valid UTF-8".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("app-template-logs-test-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn rotate_if_needed_leaves_small_files_alone() {
+        let path = temp_path("small.log");
+        fs::write(&path, b"just a few bytes").unwrap();
+
+        rotate_if_needed(&path);
+
+        assert!(path.exists());
+        assert!(!path.with_extension("log.1").exists());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn rotate_if_needed_rotates_oversized_files() {
+        let path = temp_path("big.log");
+        let rotated = path.with_extension("log.1");
+        fs::write(&path, vec![0u8; LOG_ROTATE_BYTES as usize]).unwrap();
+
+        rotate_if_needed(&path);
+
+        assert!(!path.exists());
+        assert!(rotated.exists());
+
+        let _ = fs::remove_file(&rotated);
+    }
+}
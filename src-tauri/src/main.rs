@@ -3,21 +3,72 @@
     windows_subsystem = "windows"
 )]
 
+mod binary;
+mod logs;
+mod protocol;
+mod shutdown;
+mod supervisor;
+
+use std::net::TcpListener;
 use std::process::{Child, Command, Stdio};
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager, State};
-use tokio::time::{sleep, Duration};
+use tokio::sync::oneshot;
+use tokio::time::Duration;
+
+use logs::{get_last_log_file, LogState};
+use supervisor::SupervisorState;
 
 // Backend process state
 #[derive(Debug)]
 struct BackendProcess(Mutex<Option<Child>>);
 
+/// Port the currently running backend is bound to. Allocated fresh on each
+/// (re)start so the template never collides with another instance on a fixed port.
+struct BackendPort(Mutex<u16>);
+
+const READY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Binds port 0 to let the OS hand back an unused port, then releases it for
+/// the child to bind. Small TOCTOU window is acceptable here - on failure the
+/// supervisor's retry loop will simply try again.
+fn allocate_free_port() -> Result<u16, String> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .map_err(|e| format!("Failed to allocate a free port: {}", e))?;
+    listener
+        .local_addr()
+        .map(|addr| addr.port())
+        .map_err(|e| format!("Failed to read allocated port: {}", e))
+}
+
+/// Resolves the backend binary (config override, bundled sidecar, or PATH) and
+/// spawns it with piped stdout/stderr on a freshly allocated port. Used both for
+/// the initial launch and by the supervisor's restart loop.
+fn spawn_backend_child(app: &AppHandle) -> Result<Child, String> {
+    let server_path = binary::resolve_server_binary(app)?;
+    let port = allocate_free_port()?;
+    *app.state::<BackendPort>().0.lock().unwrap() = port;
+
+    println!("Starting server from: {:?} on port {}", server_path, port);
+
+    // Start the resolved server binary
+    Command::new(&server_path)
+        .env("TAURI", "1")  // Set desktop mode
+        .env("NODE_ENV", "production")
+        .env("PORT", port.to_string())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start server binary {}: {}", server_path.display(), e))
+}
+
 #[tauri::command]
 async fn start_backend(app: AppHandle, state: State<'_, BackendProcess>) -> Result<(), String> {
-    let mut process_guard = state.0.lock().unwrap();
-    
-    if process_guard.is_some() {
-        return Ok(()); // Already running
+    {
+        let process_guard = state.0.lock().unwrap();
+        if process_guard.is_some() {
+            return Ok(()); // Already running
+        }
     }
 
     if cfg!(debug_assertions) {
@@ -26,57 +77,47 @@ async fn start_backend(app: AppHandle, state: State<'_, BackendProcess>) -> Resu
         return Ok(());
     }
 
-    // Production mode - use bundled sidecar binary
-    let resource_dir = app.path().resource_dir()
-        .map_err(|e| format!("Failed to resolve resource directory: {}", e))?;
+    // `BackendProcess` isn't populated until the supervisor task has resolved the
+    // binary, bound a port, and spawned the child, so the guard above can't stop
+    // two back-to-back calls (e.g. a double-clicked retry button) from both
+    // spawning a supervisor. Claim the right to start atomically instead.
+    if !app.state::<SupervisorState>().try_start() {
+        return Ok(()); // A start is already in progress
+    }
 
-    // Determine the binary name based on target triple
-    let binary_name = if cfg!(target_os = "macos") {
-        if cfg!(target_arch = "aarch64") {
-            "server-aarch64-apple-darwin"
-        } else {
-            "server-x86_64-apple-darwin"
-        }
-    } else if cfg!(target_os = "windows") {
-        "server-x86_64-pc-windows-msvc.exe"
-    } else {
-        "server-x86_64-unknown-linux-gnu"
-    };
+    let (ready_tx, ready_rx) = oneshot::channel();
+    supervisor::spawn_supervisor(app, spawn_backend_child, Some(ready_tx));
+
+    match tokio::time::timeout(READY_TIMEOUT, ready_rx).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err("Backend supervisor stopped before the server became ready".to_string()),
+        Err(_) => Err("Timed out waiting for the backend to become ready".to_string()),
+    }
+}
 
-    let server_path = resource_dir.join("binaries").join(binary_name);
-    
-    println!("Starting server from: {:?}", server_path);
+#[tauri::command]
+async fn stop_backend(app: AppHandle, state: State<'_, BackendProcess>) -> Result<(), String> {
+    // Tell the supervisor this is an intentional stop and wait for its task to
+    // actually finish, so a follow-up start/restart never races a lingering
+    // old supervisor loop.
+    app.state::<SupervisorState>().stop_and_join().await;
 
-    // Start the bundled server binary
-    let child = Command::new(&server_path)
-        .env("TAURI", "1")  // Set desktop mode
-        .env("NODE_ENV", "production")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start server binary {}: {}", server_path.display(), e))?;
+    let child = state.0.lock().unwrap().take();
+    let Some(mut child) = child else {
+        return Ok(()); // Already stopped
+    };
+
+    let port = *app.state::<BackendPort>().0.lock().unwrap();
+    shutdown::graceful_stop(&mut child, port).await;
 
-    *process_guard = Some(child);
-    println!("Server started successfully");
+    println!("Backend stopped successfully");
     Ok(())
 }
 
 #[tauri::command]
-fn stop_backend(state: State<BackendProcess>) -> Result<(), String> {
-    let mut process_guard = state.0.lock().unwrap();
-    
-    if let Some(mut child) = process_guard.take() {
-        match child.kill() {
-            Ok(_) => {
-                let _ = child.wait();
-                println!("Backend stopped successfully");
-                Ok(())
-            }
-            Err(e) => Err(format!("Failed to stop backend: {}", e))
-        }
-    } else {
-        Ok(()) // Already stopped
-    }
+async fn restart_backend(app: AppHandle, state: State<'_, BackendProcess>) -> Result<(), String> {
+    stop_backend(app.clone(), state.clone()).await?;
+    start_backend(app, state).await
 }
 
 #[tauri::command]
@@ -97,23 +138,34 @@ fn get_backend_status(state: State<BackendProcess>) -> String {
 
 #[tauri::command]
 fn get_api_url() -> String {
-    // Return the expected API URL for the StandardServer
-    "http://localhost:8080".to_string()
+    // Frontend requests go through the `appapi://` scheme handler, which proxies
+    // into the backend - no fixed port, no CORS.
+    format!("{}://localhost", protocol::SCHEME)
 }
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .manage(BackendProcess(Mutex::new(None)))
+        .manage(BackendPort(Mutex::new(0)))
+        .manage(SupervisorState::default())
+        .register_asynchronous_uri_scheme_protocol(protocol::SCHEME, |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(protocol::proxy_request(&app, request).await);
+            });
+        })
         .setup(|app| {
-            // Start backend automatically
+            // Track where backend stdout/stderr gets mirrored on disk.
+            let log_state = LogState::init(app.handle())?;
+            app.manage(log_state);
+
+            // Start backend automatically. `start_backend` only resolves once the
+            // readiness probe succeeds, so there's no race with the UI hitting the
+            // API before the server is listening.
             let app_handle = app.handle().clone();
-            
+
             tauri::async_runtime::spawn(async move {
-                // Wait a moment for the app to initialize
-                sleep(Duration::from_millis(1000)).await;
-                
-                // Get the state from the app handle
                 let backend_state = app_handle.state::<BackendProcess>();
                 if let Err(e) = start_backend(app_handle.clone(), backend_state).await {
                     eprintln!("Failed to auto-start backend: {}", e);
@@ -141,22 +193,30 @@ fn main() {
             }
             
             println!("✨ EpiSensor App Template initialized");
-            println!("🌐 Backend API: http://localhost:8080");
+            println!("🌐 Backend API: {}://localhost", protocol::SCHEME);
             
             Ok(())
         })
         .on_window_event(|window, event| {
-            // Clean up backend when window closes
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
-                let state = window.state::<BackendProcess>();
-                let _ = stop_backend(state);
+            // Hold the window open until the backend has shut down gracefully,
+            // without blocking the event loop thread on the (up to ~15s) sequence.
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                api.prevent_close();
+                let app = window.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = app.state::<BackendProcess>();
+                    let _ = stop_backend(app.clone(), state).await;
+                    app.exit(0);
+                });
             }
         })
         .invoke_handler(tauri::generate_handler![
             start_backend,
             stop_backend,
+            restart_backend,
             get_backend_status,
-            get_api_url
+            get_api_url,
+            get_last_log_file
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
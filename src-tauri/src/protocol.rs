@@ -0,0 +1,79 @@
+use tauri::http::{Request, Response, StatusCode};
+use tauri::{AppHandle, Manager};
+
+use crate::BackendPort;
+
+/// Custom scheme the frontend talks to instead of a fixed `http://localhost:PORT`,
+/// avoiding both port collisions and cross-origin restrictions.
+pub const SCHEME: &str = "appapi";
+
+/// Proxies a request received on the `appapi://` scheme to the locally bound
+/// backend, converting between Tauri's and reqwest's request/response types.
+pub async fn proxy_request(app: &AppHandle, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let port = *app.state::<BackendPort>().0.lock().unwrap();
+    let target = target_url(&request, port);
+
+    let method = reqwest::Method::from_bytes(request.method().as_str().as_bytes())
+        .unwrap_or(reqwest::Method::GET);
+
+    let client = reqwest::Client::new();
+    let mut builder = client.request(method, &target).body(request.body().clone());
+
+    for (name, value) in request.headers() {
+        if let Ok(value_str) = value.to_str() {
+            builder = builder.header(name.as_str(), value_str);
+        }
+    }
+
+    match builder.send().await {
+        Ok(response) => convert_response(response).await,
+        Err(e) => error_response(format!("Failed to reach backend at {}: {}", target, e)),
+    }
+}
+
+fn target_url(request: &Request<Vec<u8>>, port: u16) -> String {
+    let path_and_query = request
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+    format!("http://localhost:{}{}", port, path_and_query)
+}
+
+async fn convert_response(response: reqwest::Response) -> Response<Vec<u8>> {
+    let status = StatusCode::from_u16(response.status().as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+    let headers = response.headers().clone();
+    let body = response.bytes().await.unwrap_or_default().to_vec();
+
+    let mut builder = Response::builder().status(status);
+    for (name, value) in headers.iter() {
+        if is_hop_by_hop_header(name.as_str()) {
+            continue;
+        }
+        builder = builder.header(name, value);
+    }
+    // The body above is already fully buffered (any `Transfer-Encoding: chunked`
+    // framing from the backend was consumed by `response.bytes()`), so set
+    // `Content-Length` explicitly rather than forwarding framing headers that no
+    // longer match what's actually being sent.
+    builder = builder.header(tauri::http::header::CONTENT_LENGTH, body.len());
+    builder.body(body).unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+/// Headers that describe per-connection framing rather than the resource
+/// itself - meaningless (or actively wrong) once the response has been
+/// re-framed as a single buffered body.
+fn is_hop_by_hop_header(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "transfer-encoding" | "connection" | "content-length"
+    )
+}
+
+fn error_response(message: String) -> Response<Vec<u8>> {
+    eprintln!("appapi proxy error: {}", message);
+    Response::builder()
+        .status(StatusCode::BAD_GATEWAY)
+        .body(message.into_bytes())
+        .unwrap_or_else(|_| Response::new(Vec::new()))
+}
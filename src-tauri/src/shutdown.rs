@@ -0,0 +1,63 @@
+use std::process::Child;
+use std::time::Duration;
+
+use tokio::time::{sleep, Instant};
+
+const SHUTDOWN_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const SIGTERM_TIMEOUT: Duration = Duration::from_secs(5);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Gives the backend a chance to flush and exit cleanly before escalating to
+/// `kill()`: first a `/shutdown` request, then (on Unix) SIGTERM, only falling
+/// back to SIGKILL if the process is still alive after both.
+pub async fn graceful_stop(child: &mut Child, port: u16) {
+    if request_shutdown(port).await && wait_with_timeout(child, SHUTDOWN_REQUEST_TIMEOUT).await {
+        return;
+    }
+
+    if send_sigterm(child) && wait_with_timeout(child, SIGTERM_TIMEOUT).await {
+        return;
+    }
+
+    eprintln!("Backend did not exit gracefully, killing it");
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+async fn request_shutdown(port: u16) -> bool {
+    let url = format!("http://localhost:{}/shutdown", port);
+    reqwest::Client::new()
+        .post(&url)
+        .timeout(SHUTDOWN_REQUEST_TIMEOUT)
+        .send()
+        .await
+        .is_ok()
+}
+
+#[cfg(unix)]
+fn send_sigterm(child: &Child) -> bool {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM).is_ok()
+}
+
+#[cfg(not(unix))]
+fn send_sigterm(_child: &Child) -> bool {
+    false
+}
+
+/// Polls `try_wait` until the child exits or `timeout` elapses.
+async fn wait_with_timeout(child: &mut Child, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) | Err(_) => return true,
+            Ok(None) => {}
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
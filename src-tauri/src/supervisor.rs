@@ -0,0 +1,274 @@
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+
+use crate::logs::spawn_log_capture;
+use crate::{BackendPort, BackendProcess};
+
+const INITIAL_BACKOFF_MS: u64 = 500;
+const MAX_BACKOFF_MS: u64 = 30_000;
+const POLL_INTERVAL_MS: u64 = 500;
+const HEALTHY_UPTIME_SECS: u64 = 30;
+const MAX_RETRIES: u32 = 10;
+const READY_TIMEOUT: Duration = Duration::from_secs(10);
+const READY_POLL_INTERVAL_MS: u64 = 200;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum BackendStatus {
+    Starting,
+    Running,
+    Restarting,
+    Failed,
+}
+
+fn emit_status(app: &AppHandle, status: BackendStatus) {
+    let _ = app.emit("backend-status", status);
+}
+
+/// Whether the supervisor should keep the backend alive, plus a handle to the
+/// currently-running supervisor task. Flipped off and joined by an intentional
+/// `stop_backend` so a clean stop isn't treated as a crash to recover from, and
+/// so `restart_backend` can't race a not-yet-finished old loop against a
+/// freshly spawned one.
+pub struct SupervisorState {
+    pub running: Arc<AtomicBool>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Default for SupervisorState {
+    fn default() -> Self {
+        SupervisorState {
+            running: Arc::new(AtomicBool::new(false)),
+            task: Mutex::new(None),
+        }
+    }
+}
+
+impl SupervisorState {
+    /// Atomically claims the right to spawn a supervisor: succeeds (and marks
+    /// `running`) only if nothing was already running. Two concurrent
+    /// `start_backend` calls (e.g. a double-clicked retry button) race this
+    /// single `compare_exchange` rather than a separate check-then-act on
+    /// `BackendProcess`, which isn't populated until well after the supervisor
+    /// task has already started - so only one of them can ever win and spawn.
+    pub fn try_start(&self) -> bool {
+        self.running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// Tells the current supervisor loop to stop and waits for its task to
+    /// actually finish (aborting it if it's parked in a backoff sleep) before
+    /// returning, so callers never end up with two supervisors running at once.
+    pub async fn stop_and_join(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        let task = self.task.lock().unwrap().take();
+        if let Some(task) = task {
+            task.abort();
+            let _ = task.await;
+        }
+    }
+}
+
+/// Spawns the backend via `spawn_child` and supervises it for as long as
+/// `SupervisorState` stays enabled, relaunching on unexpected exit or a failed
+/// readiness probe with bounded exponential backoff (500ms -> 30s), giving up
+/// after `MAX_RETRIES` in a row. `ready_tx`, if given, resolves once the backend
+/// first answers its health check (or the supervisor gives up before that).
+/// Caller must have already won `SupervisorState::try_start` before calling
+/// this, so `running` is already `true` by the time the loop below checks it.
+pub fn spawn_supervisor<F>(
+    app: AppHandle,
+    spawn_child: F,
+    ready_tx: Option<oneshot::Sender<Result<(), String>>>,
+) where
+    F: Fn(&AppHandle) -> Result<Child, String> + Send + Sync + 'static,
+{
+    let app_for_state = app.clone();
+    let supervising = app.state::<SupervisorState>().running.clone();
+
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut ready_tx = ready_tx;
+        let mut backoff_ms = INITIAL_BACKOFF_MS;
+        let mut retries = 0u32;
+
+        while supervising.load(Ordering::SeqCst) {
+            emit_status(&app, BackendStatus::Starting);
+
+            let mut child = match spawn_child(&app) {
+                Ok(child) => child,
+                Err(e) => {
+                    eprintln!("Failed to start backend: {}", e);
+                    if !bump_retries_or_give_up(&app, &mut retries, &mut ready_tx) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = next_backoff(backoff_ms);
+                    continue;
+                }
+            };
+
+            if let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) {
+                spawn_log_capture(app.clone(), stdout, stderr);
+            }
+
+            *app.state::<BackendProcess>().0.lock().unwrap() = Some(child);
+
+            let port = *app.state::<BackendPort>().0.lock().unwrap();
+            if !wait_until_ready(port).await {
+                eprintln!("Backend on port {} did not become ready in time", port);
+                if let Some(mut child) = app.state::<BackendProcess>().0.lock().unwrap().take() {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+                if !bump_retries_or_give_up(&app, &mut retries, &mut ready_tx) {
+                    break;
+                }
+                sleep(Duration::from_millis(backoff_ms)).await;
+                backoff_ms = next_backoff(backoff_ms);
+                continue;
+            }
+
+            emit_status(&app, BackendStatus::Running);
+            if let Some(tx) = ready_tx.take() {
+                let _ = tx.send(Ok(()));
+            }
+
+            let uptime = wait_for_exit(&app, &supervising).await;
+
+            if !supervising.load(Ordering::SeqCst) {
+                break;
+            }
+
+            // Clear the exited child out of shared state so `get_backend_status`
+            // reports "stopped" while we decide whether to relaunch.
+            *app.state::<BackendProcess>().0.lock().unwrap() = None;
+
+            if uptime >= Duration::from_secs(HEALTHY_UPTIME_SECS) {
+                backoff_ms = INITIAL_BACKOFF_MS;
+                retries = 0;
+            } else if !bump_retries_or_give_up(&app, &mut retries, &mut ready_tx) {
+                break;
+            }
+
+            emit_status(&app, BackendStatus::Restarting);
+            sleep(Duration::from_millis(backoff_ms)).await;
+            backoff_ms = next_backoff(backoff_ms);
+        }
+
+        // Release the claim taken by `try_start` so a later start can spawn again.
+        supervising.store(false, Ordering::SeqCst);
+    });
+
+    *app_for_state.state::<SupervisorState>().task.lock().unwrap() = Some(handle);
+}
+
+/// Polls the managed child until it exits or supervision is turned off,
+/// returning how long it stayed up.
+async fn wait_for_exit(app: &AppHandle, supervising: &Arc<AtomicBool>) -> Duration {
+    let started_at = Instant::now();
+    loop {
+        sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+
+        let exited = {
+            let state = app.state::<BackendProcess>();
+            let mut guard = state.0.lock().unwrap();
+            match guard.as_mut() {
+                Some(child) => matches!(child.try_wait(), Ok(Some(_)) | Err(_)),
+                None => true,
+            }
+        };
+
+        if exited || !supervising.load(Ordering::SeqCst) {
+            return started_at.elapsed();
+        }
+    }
+}
+
+/// Polls `GET /health` on the given port until it answers successfully or
+/// `READY_TIMEOUT` elapses.
+async fn wait_until_ready(port: u16) -> bool {
+    let client = reqwest::Client::new();
+    let url = format!("http://localhost:{}/health", port);
+    let deadline = Instant::now() + READY_TIMEOUT;
+
+    while Instant::now() < deadline {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if let Ok(response) = client.get(&url).timeout(remaining).send().await {
+            if response.status().is_success() {
+                return true;
+            }
+        }
+        sleep(Duration::from_millis(READY_POLL_INTERVAL_MS)).await;
+    }
+    false
+}
+
+/// Doubles the backoff delay, capped at `MAX_BACKOFF_MS`.
+fn next_backoff(current: u64) -> u64 {
+    (current * 2).min(MAX_BACKOFF_MS)
+}
+
+/// Increments the retry counter and reports whether it has exceeded `MAX_RETRIES`.
+fn retries_exhausted(retries: &mut u32) -> bool {
+    *retries += 1;
+    *retries > MAX_RETRIES
+}
+
+/// Increments the retry counter, and once `MAX_RETRIES` is exceeded emits the
+/// terminal `backend-failed` event (plus a final `backend-status: failed`),
+/// resolves a still-pending `ready_tx` with an error, and returns `false` to
+/// tell the caller to stop supervising.
+fn bump_retries_or_give_up(
+    app: &AppHandle,
+    retries: &mut u32,
+    ready_tx: &mut Option<oneshot::Sender<Result<(), String>>>,
+) -> bool {
+    if retries_exhausted(retries) {
+        emit_status(app, BackendStatus::Failed);
+        let _ = app.emit("backend-failed", MAX_RETRIES);
+        if let Some(tx) = ready_tx.take() {
+            let _ = tx.send(Err("Backend failed to start after repeated retries".to_string()));
+        }
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_backoff_doubles_until_capped() {
+        let mut backoff = INITIAL_BACKOFF_MS;
+        assert_eq!(next_backoff(backoff), 1_000);
+        backoff = next_backoff(backoff);
+        backoff = next_backoff(backoff);
+        assert_eq!(backoff, 4_000);
+
+        // Keep doubling past the cap and confirm it holds there.
+        for _ in 0..20 {
+            backoff = next_backoff(backoff);
+        }
+        assert_eq!(backoff, MAX_BACKOFF_MS);
+    }
+
+    #[test]
+    fn retries_exhausted_after_max_retries() {
+        let mut retries = 0u32;
+        for _ in 0..MAX_RETRIES {
+            assert!(!retries_exhausted(&mut retries));
+        }
+        assert!(retries_exhausted(&mut retries));
+        assert_eq!(retries, MAX_RETRIES + 1);
+    }
+}